@@ -0,0 +1,231 @@
+//! Incremental, persisted analysis cache.
+//!
+//! Caches per-file analysis artifacts — symbol tables, intra-file call
+//! edges, and escaped-function-value sets — keyed by a file's path and
+//! a content hash of it, so re-running the analyzer over a large
+//! workspace only re-parses files whose content actually changed.
+//! Content hash alone isn't enough: a symbol's id also bakes in its
+//! file path, so two files with identical content still need distinct
+//! cache entries. The merged call graph from the last full run is
+//! stored separately from the per-file entries it was built from.
+//! Backed by an embedded key-value store (`sled`); writes go through a
+//! transaction so a crashed or aborted run can never leave a graph that
+//! doesn't match what's cached per file.
+
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use sled::Transactional;
+
+use crate::analysis::model::{CallEdge, PendingMethodCall, PendingReference, Symbol};
+
+/// Stable content hash used as a cache key. Unlike the crate's old
+/// `DefaultHasher`-based helper, this is a fixed algorithm (FNV-1a) with
+/// no per-process seed, so the same file content always produces the
+/// same key across runs and machines.
+pub type ContentHash = u64;
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+const FNV_PRIME: u64 = 0x100000001b3;
+
+/// Hash file content into a stable [`ContentHash`] suitable for use as a
+/// cache key.
+pub fn content_hash(bytes: &[u8]) -> ContentHash {
+    let mut hash = FNV_OFFSET_BASIS;
+    for &byte in bytes {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(FNV_PRIME);
+    }
+    hash
+}
+
+/// The sled key a file's cache entry lives under: its path plus its
+/// content hash, not the content hash alone. A symbol's id is derived
+/// from `(file, name, line)`
+/// ([`crate::analysis::extract::symbol_id`]), so two distinct files that
+/// happen to share identical content — an empty `mod.rs` repeated
+/// across modules — still produce artifacts with different ids; keying
+/// by content hash alone would let one file's cached entry silently
+/// stand in for the other's on a later run.
+fn cache_key(path: &Path, hash: ContentHash) -> Vec<u8> {
+    let mut key = path.to_string_lossy().into_owned().into_bytes();
+    key.push(0);
+    key.extend_from_slice(&hash.to_be_bytes());
+    key
+}
+
+/// Everything cached for one source file: its extracted symbols, the
+/// call-graph edges resolved within that file, and the references that
+/// couldn't be — method calls whose receiver type is unknown, and plain
+/// named calls or escapes that point outside the file (resolved against
+/// the merged cross-file graph once every file has been extracted). An
+/// edge's [`EdgeKind`](crate::analysis::model::EdgeKind) already
+/// distinguishes a direct call from a function value escaping, so no
+/// separate set is needed for the latter.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FileArtifacts {
+    pub symbols: Vec<Symbol>,
+    pub edges: Vec<CallEdge>,
+    pub pending_method_calls: Vec<PendingMethodCall>,
+    pub pending_references: Vec<PendingReference>,
+}
+
+/// Persisted analysis cache. Per-file artifacts live under the `files`
+/// tree keyed by path and content hash; the merged call graph from the last full
+/// run lives under its own `graph` tree, so a reader never observes one
+/// updated without the other.
+pub struct AnalysisCache {
+    files: sled::Tree,
+    graph: sled::Tree,
+}
+
+impl AnalysisCache {
+    /// Open (creating if needed) the cache database at `path`.
+    pub fn open(path: &Path) -> sled::Result<Self> {
+        let db = sled::open(path)?;
+        Ok(Self {
+            files: db.open_tree("files")?,
+            graph: db.open_tree("graph")?,
+        })
+    }
+
+    /// Open a temporary, in-memory cache that's dropped once it goes out
+    /// of scope — useful for a one-off run with no on-disk persistence,
+    /// and in tests.
+    pub fn open_temporary() -> sled::Result<Self> {
+        let db = sled::Config::new().temporary(true).open()?;
+        Ok(Self {
+            files: db.open_tree("files")?,
+            graph: db.open_tree("graph")?,
+        })
+    }
+
+    /// Look up cached artifacts for a file by its path and content hash.
+    /// A miss means the file is new or has changed and must be
+    /// re-parsed.
+    pub fn get(&self, path: &Path, hash: ContentHash) -> sled::Result<Option<FileArtifacts>> {
+        Ok(self
+            .files
+            .get(cache_key(path, hash))?
+            .map(|bytes| bincode::deserialize(&bytes).expect("corrupt cache entry")))
+    }
+
+    /// Write one file's artifacts and the recomputed merged graph as a
+    /// single transaction, so a crash between the two writes can never
+    /// leave the graph out of sync with what's cached per file.
+    pub fn put_with_graph(
+        &self,
+        path: &Path,
+        hash: ContentHash,
+        artifacts: &FileArtifacts,
+        graph_key: &str,
+        graph_bytes: &[u8],
+    ) -> sled::transaction::TransactionResult<()> {
+        (&self.files, &self.graph).transaction(|(files, graph)| {
+            let artifact_bytes = bincode::serialize(artifacts).expect("serializable artifacts");
+            files.insert(cache_key(path, hash), artifact_bytes)?;
+            graph.insert(graph_key.as_bytes(), graph_bytes)?;
+            Ok(())
+        })
+    }
+
+    /// Drop every cached entry — the library-level escape hatch for when
+    /// a run must not trust any prior result. There's no `--no-cache`
+    /// CLI flag in this crate (it has no binary); a host that wants one
+    /// calls this directly before the first `get`/`put_with_graph` of a
+    /// run.
+    pub fn invalidate(&self) -> sled::Result<()> {
+        self.files.clear()?;
+        self.graph.clear()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::model::EdgeKind;
+    use std::path::Path;
+
+    #[test]
+    fn a_put_file_is_a_hit_on_a_later_run_and_a_miss_once_content_changes() {
+        let cache = AnalysisCache::open_temporary().expect("open an in-memory cache for the test");
+        let path = Path::new("src/lib.rs");
+        let hash = content_hash(b"fn f() {}");
+
+        assert!(
+            cache.get(path, hash).unwrap().is_none(),
+            "nothing cached yet, so the first run should see a miss"
+        );
+
+        let artifacts = FileArtifacts {
+            symbols: Vec::new(),
+            edges: vec![CallEdge {
+                from: 1,
+                to: 2,
+                kind: EdgeKind::Call,
+            }],
+            pending_method_calls: Vec::new(),
+            pending_references: Vec::new(),
+        };
+        cache
+            .put_with_graph(path, hash, &artifacts, "main", b"graph-bytes")
+            .expect("transaction succeeds");
+
+        let cached = cache
+            .get(path, hash)
+            .unwrap()
+            .expect("a later run should see a cache hit for unchanged content");
+        assert_eq!(cached.edges.len(), 1);
+
+        let changed_hash = content_hash(b"fn f() { changed(); }");
+        assert!(
+            cache.get(path, changed_hash).unwrap().is_none(),
+            "changed content hashes to a different key and should still miss"
+        );
+    }
+
+    #[test]
+    fn two_files_with_identical_content_each_keep_their_own_cache_entry() {
+        let cache = AnalysisCache::open_temporary().expect("open an in-memory cache for the test");
+        let hash = content_hash(b"pub fn only_in_a() {}");
+        let path_a = Path::new("a.rs");
+        let path_b = Path::new("b.rs");
+
+        let artifacts_a = FileArtifacts {
+            symbols: vec![],
+            edges: vec![CallEdge {
+                from: 1,
+                to: 2,
+                kind: EdgeKind::Call,
+            }],
+            pending_method_calls: Vec::new(),
+            pending_references: Vec::new(),
+        };
+        let artifacts_b = FileArtifacts {
+            symbols: vec![],
+            edges: vec![CallEdge {
+                from: 3,
+                to: 4,
+                kind: EdgeKind::Call,
+            }],
+            pending_method_calls: Vec::new(),
+            pending_references: Vec::new(),
+        };
+
+        cache
+            .put_with_graph(path_a, hash, &artifacts_a, "main", b"graph-bytes")
+            .expect("transaction succeeds");
+        cache
+            .put_with_graph(path_b, hash, &artifacts_b, "main", b"graph-bytes")
+            .expect("transaction succeeds");
+
+        let cached_a = cache.get(path_a, hash).unwrap().expect("a.rs was cached");
+        let cached_b = cache.get(path_b, hash).unwrap().expect("b.rs was cached");
+        assert_eq!(
+            cached_a.edges[0].from, 1,
+            "a.rs's own cache entry shouldn't have been overwritten by b.rs's write, even though both share a content hash"
+        );
+        assert_eq!(cached_b.edges[0].from, 3);
+    }
+}
@@ -0,0 +1,495 @@
+//! Source-to-graph extraction: parse a Rust source file into the
+//! symbols, call edges, and escaped-function-value edges that
+//! [`crate::analysis::reachability`] runs its fixpoint over.
+//!
+//! This is the one piece of the pipeline that actually looks at Rust
+//! syntax — everything downstream (the graph, the fixpoint, the cache,
+//! the parallel front-end) treats a file's [`FileArtifacts`] as opaque
+//! data. A symbol's id is derived deterministically from
+//! `(file, name, declaration line)` via the crate's stable content hash,
+//! so two files extracted independently — in parallel, or replayed from
+//! cache — get distinct ids even though neither extraction knows about
+//! the other, barring a collision of the 32-bit id the hash is
+//! truncated to.
+//!
+//! Method calls can't be resolved within a single file, since the
+//! receiver's type (and therefore which file defines the method) isn't
+//! known without whole-crate type information; those are recorded as
+//! [`PendingMethodCall`]s and resolved conservatively by name and arity
+//! once the full cross-file graph is assembled
+//! (see [`CallGraph::resolve_method_candidates`]).
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use syn::parse::Parser;
+use syn::punctuated::Punctuated;
+use syn::visit::{self, Visit};
+use syn::{
+    Expr, ExprCall, ExprMethodCall, ExprPath, FnArg, ImplItem, ImplItemFn, Item, ItemEnum, ItemFn,
+    ItemImpl, ItemStruct, ItemTrait, ItemType, Macro, Token, Visibility as SynVisibility,
+};
+
+use crate::analysis::cache::{content_hash, FileArtifacts};
+use crate::analysis::model::{
+    CallEdge, EdgeKind, PendingMethodCall, PendingReference, Symbol, SymbolId, SymbolKind, Visibility,
+};
+use crate::analysis::reachability::CallGraph;
+
+/// Parse `content` (the text of `file`) and return every symbol, edge,
+/// and pending method call found in it. A file that fails to parse
+/// (e.g. a non-Rust fixture, or a syntax error) yields empty artifacts
+/// rather than failing the whole run.
+pub fn extract_file(file: &Path, content: &[u8]) -> FileArtifacts {
+    let text = String::from_utf8_lossy(content);
+    let parsed = match syn::parse_file(&text) {
+        Ok(parsed) => parsed,
+        Err(_) => return FileArtifacts::default(),
+    };
+
+    let mut known = HashMap::new();
+    collect_declarations(&parsed.items, file, &mut known);
+
+    let mut visitor = CallVisitor {
+        known: &known,
+        current_fn: None,
+        current_impl: None,
+        edges: Vec::new(),
+        pending_methods: Vec::new(),
+        pending_references: Vec::new(),
+    };
+    visitor.visit_file(&parsed);
+    let edges = visitor.edges;
+    let pending_method_calls = visitor.pending_methods;
+    let pending_references = visitor.pending_references;
+
+    FileArtifacts {
+        symbols: known.into_values().collect(),
+        edges,
+        pending_method_calls,
+        pending_references,
+    }
+}
+
+/// Resolve every pending method call against the merged graph,
+/// conservatively by name and arity, and add the resulting call edges
+/// in place. Cross-file references are resolved separately by
+/// [`resolve_pending_references`].
+pub fn resolve_pending_method_calls(graph: &mut CallGraph, pending: &[PendingMethodCall]) {
+    for call in pending {
+        for candidate in graph.resolve_method_candidates(&call.method_name, call.arity) {
+            graph.add_call(call.from, candidate);
+        }
+    }
+}
+
+/// Resolve every pending cross-file reference (a call or value escape
+/// that single-file extraction couldn't find locally) against the
+/// merged graph, by exact function name.
+pub fn resolve_pending_references(graph: &mut CallGraph, pending: &[PendingReference]) {
+    for reference in pending {
+        for candidate in graph.resolve_function_candidates(&reference.name) {
+            match reference.kind {
+                EdgeKind::Call => graph.add_call(reference.from, candidate),
+                EdgeKind::ValueEscape => graph.add_value_escape(reference.from, candidate),
+            }
+        }
+    }
+}
+
+/// Deterministically derive a [`SymbolId`] from a symbol's declaration
+/// site so independently-extracted files need no shared id counter.
+/// The 64-bit content hash is truncated to a 32-bit [`SymbolId`].
+fn symbol_id(file: &Path, name: &str, line: usize) -> SymbolId {
+    let key = format!("{}:{}:{}", file.display(), name, line);
+    content_hash(key.as_bytes()) as SymbolId
+}
+
+fn visibility_of(vis: &SynVisibility) -> Visibility {
+    match vis {
+        SynVisibility::Public(_) => Visibility::Public,
+        SynVisibility::Restricted(_) => Visibility::PublicCrate,
+        SynVisibility::Inherited => Visibility::Private,
+    }
+}
+
+fn has_attr(attrs: &[syn::Attribute], name: &str) -> bool {
+    attrs.iter().any(|a| a.path().is_ident(name))
+}
+
+fn function_symbol(file: &Path, f: &ItemFn) -> Symbol {
+    let name = f.sig.ident.to_string();
+    let line = f.sig.ident.span().start().line;
+    let sig = &f.sig;
+    Symbol {
+        id: symbol_id(file, &name, line),
+        signature: quote::quote!(#sig).to_string(),
+        kind: SymbolKind::Function,
+        visibility: visibility_of(&f.vis),
+        file: file.to_path_buf(),
+        line: line as u32,
+        arity: f.sig.inputs.len(),
+        is_test: has_attr(&f.attrs, "test"),
+        is_bench: has_attr(&f.attrs, "bench"),
+        name,
+    }
+}
+
+fn method_symbol(file: &Path, m: &ImplItemFn) -> Symbol {
+    let name = m.sig.ident.to_string();
+    let line = m.sig.ident.span().start().line;
+    let arity = m
+        .sig
+        .inputs
+        .iter()
+        .filter(|arg| !matches!(arg, FnArg::Receiver(_)))
+        .count();
+    let sig = &m.sig;
+    Symbol {
+        id: symbol_id(file, &name, line),
+        signature: quote::quote!(#sig).to_string(),
+        kind: SymbolKind::Method,
+        visibility: visibility_of(&m.vis),
+        file: file.to_path_buf(),
+        line: line as u32,
+        arity,
+        is_test: has_attr(&m.attrs, "test"),
+        is_bench: has_attr(&m.attrs, "bench"),
+        name,
+    }
+}
+
+fn struct_symbol(file: &Path, s: &ItemStruct) -> Symbol {
+    let name = s.ident.to_string();
+    let line = s.ident.span().start().line;
+    Symbol {
+        id: symbol_id(file, &name, line),
+        signature: quote::quote!(#s).to_string(),
+        kind: SymbolKind::Struct,
+        visibility: visibility_of(&s.vis),
+        file: file.to_path_buf(),
+        line: line as u32,
+        arity: s.fields.len(),
+        is_test: false,
+        is_bench: false,
+        name,
+    }
+}
+
+fn enum_symbol(file: &Path, e: &ItemEnum) -> Symbol {
+    let name = e.ident.to_string();
+    let line = e.ident.span().start().line;
+    Symbol {
+        id: symbol_id(file, &name, line),
+        signature: quote::quote!(#e).to_string(),
+        kind: SymbolKind::Enum,
+        visibility: visibility_of(&e.vis),
+        file: file.to_path_buf(),
+        line: line as u32,
+        arity: e.variants.len(),
+        is_test: false,
+        is_bench: false,
+        name,
+    }
+}
+
+fn trait_symbol(file: &Path, t: &ItemTrait) -> Symbol {
+    let name = t.ident.to_string();
+    let line = t.ident.span().start().line;
+    Symbol {
+        id: symbol_id(file, &name, line),
+        signature: quote::quote!(#t).to_string(),
+        kind: SymbolKind::Trait,
+        visibility: visibility_of(&t.vis),
+        file: file.to_path_buf(),
+        line: line as u32,
+        arity: t.items.len(),
+        is_test: false,
+        is_bench: false,
+        name,
+    }
+}
+
+fn type_alias_symbol(file: &Path, t: &ItemType) -> Symbol {
+    let name = t.ident.to_string();
+    let line = t.ident.span().start().line;
+    Symbol {
+        id: symbol_id(file, &name, line),
+        signature: quote::quote!(#t).to_string(),
+        kind: SymbolKind::TypeAlias,
+        visibility: visibility_of(&t.vis),
+        file: file.to_path_buf(),
+        line: line as u32,
+        arity: 0,
+        is_test: false,
+        is_bench: false,
+        name,
+    }
+}
+
+/// Key a declaration by name alone within its enclosing `impl` type (or
+/// `None` for free functions), since the same method name routinely
+/// recurs across unrelated `impl` blocks in one file (every `new`,
+/// every `Default::default`) and bare-name keying would let the last
+/// one collected silently clobber the rest.
+type DeclKey = (Option<String>, String);
+
+/// The name of the type an `impl` block is for, used to scope method
+/// declarations by their enclosing type. `None` for `impl` blocks whose
+/// self type isn't a simple path (rare enough not to bother
+/// disambiguating further).
+fn impl_type_name(item_impl: &ItemImpl) -> Option<String> {
+    match item_impl.self_ty.as_ref() {
+        syn::Type::Path(type_path) => type_path.path.segments.last().map(|s| s.ident.to_string()),
+        _ => None,
+    }
+}
+
+fn collect_declarations(items: &[Item], file: &Path, known: &mut HashMap<DeclKey, Symbol>) {
+    for item in items {
+        match item {
+            Item::Fn(f) => {
+                known.insert((None, f.sig.ident.to_string()), function_symbol(file, f));
+            }
+            Item::Struct(s) => {
+                known.insert((None, s.ident.to_string()), struct_symbol(file, s));
+            }
+            Item::Enum(e) => {
+                known.insert((None, e.ident.to_string()), enum_symbol(file, e));
+            }
+            Item::Trait(t) => {
+                known.insert((None, t.ident.to_string()), trait_symbol(file, t));
+            }
+            Item::Type(t) => {
+                known.insert((None, t.ident.to_string()), type_alias_symbol(file, t));
+            }
+            Item::Impl(item_impl) => {
+                let impl_type = impl_type_name(item_impl);
+                for impl_item in &item_impl.items {
+                    if let ImplItem::Fn(m) = impl_item {
+                        known.insert(
+                            (impl_type.clone(), m.sig.ident.to_string()),
+                            method_symbol(file, m),
+                        );
+                    }
+                }
+            }
+            // `#[cfg(test)] mod tests { ... }` and any other nested
+            // module are declared inline in the same file, so their
+            // `#[test]`/`#[bench]` items need to reach the graph the
+            // same as top-level ones — recurse rather than treating the
+            // module boundary as opaque.
+            Item::Mod(m) => {
+                if let Some((_, items)) = &m.content {
+                    collect_declarations(items, file, known);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// What a call or path expression's segments resolve to before lookup:
+/// a bare name (a free function, or a local declared elsewhere), or a
+/// `Type::name` qualified path — an associated function call or a UFCS
+/// method reference — keyed by its last segment the same way
+/// [`impl_type_name`] scopes a method's own declaration.
+enum CallTarget {
+    Free(String),
+    Qualified(Option<String>, String),
+}
+
+/// Classify a call or path expression's segments as a [`CallTarget`].
+/// `Config::new` and `crate::models::Config::new` both resolve to
+/// `Qualified(Some("Config"), "new")` — only the last two segments
+/// matter, since that's all `impl_type_name` ever recorded for the
+/// declaration itself.
+fn call_target(path: &syn::Path) -> CallTarget {
+    let name = path.segments.last().expect("a path has at least one segment").ident.to_string();
+    if path.segments.len() == 1 {
+        return CallTarget::Free(name);
+    }
+    let type_name = path.segments[path.segments.len() - 2].ident.to_string();
+    CallTarget::Qualified(Some(type_name), name)
+}
+
+/// Walks function and method bodies, recording a [`EdgeKind::Call`] for
+/// every direct call to a function known in this file, an
+/// [`EdgeKind::ValueEscape`] for every other reference to that
+/// function's path (stored, passed, or returned), a
+/// [`PendingMethodCall`] for every method call (since resolving it needs
+/// the whole-crate graph), and a [`PendingReference`] for any named call
+/// or path reference that isn't declared in this file (since it may be
+/// declared in another one).
+struct CallVisitor<'a> {
+    known: &'a HashMap<DeclKey, Symbol>,
+    current_fn: Option<SymbolId>,
+    /// The enclosing `impl` type's name, mirrored from
+    /// [`collect_declarations`] so a method lookup here uses the same
+    /// `DeclKey` the declaration was stored under. `None` outside any
+    /// `impl` block.
+    current_impl: Option<String>,
+    edges: Vec<CallEdge>,
+    pending_methods: Vec<PendingMethodCall>,
+    pending_references: Vec<PendingReference>,
+}
+
+impl<'ast> Visit<'ast> for CallVisitor<'_> {
+    fn visit_item_impl(&mut self, node: &'ast ItemImpl) {
+        let prev = self.current_impl.take();
+        self.current_impl = impl_type_name(node);
+        visit::visit_item_impl(self, node);
+        self.current_impl = prev;
+    }
+
+    fn visit_item_fn(&mut self, node: &'ast ItemFn) {
+        let prev = self.current_fn;
+        let key = (None, node.sig.ident.to_string());
+        self.current_fn = self.known.get(&key).map(|s| s.id);
+        visit::visit_item_fn(self, node);
+        self.current_fn = prev;
+    }
+
+    fn visit_impl_item_fn(&mut self, node: &'ast ImplItemFn) {
+        let prev = self.current_fn;
+        let key = (self.current_impl.clone(), node.sig.ident.to_string());
+        self.current_fn = self.known.get(&key).map(|s| s.id);
+        visit::visit_impl_item_fn(self, node);
+        self.current_fn = prev;
+    }
+
+    fn visit_expr_call(&mut self, node: &'ast ExprCall) {
+        let callee = match node.func.as_ref() {
+            Expr::Path(ExprPath { path, .. }) => Some(call_target(path)),
+            _ => None,
+        };
+
+        match (self.current_fn, &callee) {
+            (Some(from), Some(CallTarget::Free(name))) => {
+                match self.known.get(&(None, name.clone())) {
+                    Some(callee) => self.edges.push(CallEdge {
+                        from,
+                        to: callee.id,
+                        kind: EdgeKind::Call,
+                    }),
+                    // Not declared in this file — it may be declared in
+                    // another one, so defer until the cross-file graph is
+                    // merged rather than dropping the call silently.
+                    None => self.pending_references.push(PendingReference {
+                        from,
+                        name: name.clone(),
+                        kind: EdgeKind::Call,
+                    }),
+                }
+            }
+            // A qualified call (`Config::new(...)`, `models::Server::new(...)`)
+            // — resolved by its last segment the same conservative way a
+            // `.method()` call is: an exact match within this file's own
+            // `impl` blocks wins outright, and anything else is deferred
+            // to name-and-arity resolution against the whole-crate graph,
+            // since the type before `::` may be an alias, a generic
+            // parameter, or declared in another file entirely.
+            (Some(from), Some(CallTarget::Qualified(type_name, name))) => {
+                match self.known.get(&(type_name.clone(), name.clone())) {
+                    Some(callee) => self.edges.push(CallEdge {
+                        from,
+                        to: callee.id,
+                        kind: EdgeKind::Call,
+                    }),
+                    None => self.pending_methods.push(PendingMethodCall {
+                        from,
+                        method_name: name.clone(),
+                        arity: node.args.len(),
+                    }),
+                }
+            }
+            // Not a simple named call (e.g. a closure expression, or a
+            // call through a field/index) — fall back to default
+            // traversal so any paths inside `node.func` still get
+            // escape-checked.
+            _ if callee.is_none() => visit::visit_expr(self, &node.func),
+            _ => {}
+        }
+
+        for arg in &node.args {
+            visit::visit_expr(self, arg);
+        }
+    }
+
+    fn visit_expr_method_call(&mut self, node: &'ast ExprMethodCall) {
+        if let Some(from) = self.current_fn {
+            self.pending_methods.push(PendingMethodCall {
+                from,
+                method_name: node.method.to_string(),
+                arity: node.args.len(),
+            });
+        }
+        visit::visit_expr_method_call(self, node);
+    }
+
+    fn visit_macro(&mut self, node: &'ast Macro) {
+        // `syn` never parses a macro's token stream into an `Expr` tree —
+        // it's opaque to the rest of this visitor by default — so a call
+        // made only as a macro argument (`println!("{}", helper())`,
+        // `lazy_static! { ... create_logger("main") ... }`) would
+        // otherwise be invisible to the call graph. Most of the macros
+        // that matter in practice (`println!`, `format!`, `write!`, and
+        // friends) expand to a comma-separated list of expressions, so
+        // reparsing the token stream that way and visiting each one
+        // recovers those calls; macros with a different grammar (e.g.
+        // `lazy_static!`'s `static ref NAME: Ty = expr;`) aren't a
+        // comma-separated expression list and are a known gap.
+        if let Ok(args) =
+            Punctuated::<Expr, Token![,]>::parse_terminated.parse2(node.tokens.clone())
+        {
+            for arg in &args {
+                self.visit_expr(arg);
+            }
+        }
+    }
+
+    fn visit_expr_path(&mut self, node: &'ast ExprPath) {
+        if let Some(from) = self.current_fn {
+            match call_target(&node.path) {
+                CallTarget::Free(name) => match self.known.get(&(None, name.clone())) {
+                    Some(callee) if callee.id != from => self.edges.push(CallEdge {
+                        from,
+                        to: callee.id,
+                        kind: EdgeKind::ValueEscape,
+                    }),
+                    Some(_) => {}
+                    // Not declared in this file — could be a function
+                    // declared elsewhere whose address escapes here, or
+                    // simply some other kind of identifier (a local
+                    // variable, a constant); deferred resolution treats
+                    // an unknown name as a no-op if it never matches a
+                    // function.
+                    None => self.pending_references.push(PendingReference {
+                        from,
+                        name,
+                        kind: EdgeKind::ValueEscape,
+                    }),
+                },
+                // A qualified path (`Config::validate` taken as a value
+                // rather than called outright) only resolves here if its
+                // declaration is in this same file — there's no
+                // cross-file deferred mechanism for a qualified escape,
+                // since `PendingMethodCall` only ever becomes a direct
+                // call edge, so an unresolved one is left alone rather
+                // than guessed at.
+                CallTarget::Qualified(type_name, name) => {
+                    if let Some(callee) = self.known.get(&(type_name, name)) {
+                        if callee.id != from {
+                            self.edges.push(CallEdge {
+                                from,
+                                to: callee.id,
+                                kind: EdgeKind::ValueEscape,
+                            });
+                        }
+                    }
+                }
+            }
+        }
+        visit::visit_expr_path(self, node);
+    }
+}
@@ -0,0 +1,17 @@
+//! Analysis pipeline: shared data model plus the passes that run over it.
+
+pub mod cache;
+pub mod extract;
+pub mod model;
+pub mod parallel;
+pub mod reachability;
+pub mod symbol_search;
+
+pub use cache::{content_hash, AnalysisCache, ContentHash, FileArtifacts};
+pub use extract::{extract_file, resolve_pending_method_calls, resolve_pending_references};
+pub use model::{
+    CallEdge, EdgeKind, PendingMethodCall, PendingReference, Symbol, SymbolId, SymbolKind, Visibility,
+};
+pub use parallel::{build_pool, extract_all, SourceFile};
+pub use reachability::{compute_reachable, compute_reachable_cancelable, dead_symbols, CallGraph};
+pub use symbol_search::{SearchHit, SymbolIndex};
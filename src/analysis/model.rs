@@ -0,0 +1,89 @@
+//! Shared data model for the analysis pipeline: symbols, call graph
+//! edges, and the small enums used to classify both.
+
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+/// Unique identifier for a symbol within a single analysis run.
+pub type SymbolId = u32;
+
+/// The kind of item a [`Symbol`] represents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SymbolKind {
+    Function,
+    Method,
+    Closure,
+    Struct,
+    Enum,
+    Trait,
+    TypeAlias,
+    Field,
+}
+
+/// Visibility of a symbol, used to seed reachability roots for library
+/// crates and to weight fuzzy-search ranking.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Visibility {
+    Public,
+    PublicCrate,
+    Private,
+}
+
+/// A single extracted symbol: where it lives, what it is, and its
+/// rendered signature.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Symbol {
+    pub id: SymbolId,
+    pub name: String,
+    pub kind: SymbolKind,
+    pub visibility: Visibility,
+    pub file: PathBuf,
+    pub line: u32,
+    pub signature: String,
+    /// Parameter count, used to conservatively resolve method calls when
+    /// the receiver type is unknown.
+    pub arity: usize,
+    pub is_test: bool,
+    pub is_bench: bool,
+}
+
+/// How one symbol refers to another: a direct call, or a reference to
+/// the callee's address that lets it escape as a function value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EdgeKind {
+    Call,
+    ValueEscape,
+}
+
+/// A directed edge in the call graph.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct CallEdge {
+    pub from: SymbolId,
+    pub to: SymbolId,
+    pub kind: EdgeKind,
+}
+
+/// A method call whose receiver type extraction couldn't determine,
+/// deferred until the full cross-file graph is available so it can be
+/// resolved conservatively by name and arity
+/// ([`CallGraph::resolve_method_candidates`](crate::analysis::CallGraph::resolve_method_candidates)).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingMethodCall {
+    pub from: SymbolId,
+    pub method_name: String,
+    pub arity: usize,
+}
+
+/// A reference to a plain (non-method) named function that extraction
+/// couldn't resolve within its own file — e.g. `main.rs` calling or
+/// taking the address of a function declared in `utils.rs`. Deferred
+/// until the full cross-file graph is available, then resolved by exact
+/// name match against every known function
+/// ([`CallGraph::resolve_function_candidates`](crate::analysis::CallGraph::resolve_function_candidates)).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingReference {
+    pub from: SymbolId,
+    pub name: String,
+    pub kind: EdgeKind,
+}
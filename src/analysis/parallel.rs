@@ -0,0 +1,154 @@
+//! Parallel per-file parsing and edge extraction.
+//!
+//! Large crates are I/O- and CPU-bound per file, so the front-end fans
+//! out parsing, symbol extraction, and intra-file call/reference-edge
+//! collection across a rayon thread pool. Each file's extraction is a
+//! pure function of its content — side-effect-free — so it's safe to
+//! run concurrently. Results are merged into a deterministically
+//! ordered map afterward, so the same input set always produces the
+//! same output regardless of scheduling order.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use rayon::prelude::*;
+
+use crate::analysis::cache::FileArtifacts;
+
+/// One source file queued for extraction.
+pub struct SourceFile {
+    pub path: PathBuf,
+    pub content: Vec<u8>,
+}
+
+/// Build a rayon thread pool with `jobs` threads, or rayon's default
+/// (the number of logical cores) when `jobs` is zero. This is a
+/// library-level knob only — the crate has no binary or arg parsing, so
+/// there's no `--jobs N` flag behind it; a host exposing one would parse
+/// it and pass the result straight through.
+pub fn build_pool(jobs: usize) -> rayon::ThreadPool {
+    let mut builder = rayon::ThreadPoolBuilder::new();
+    if jobs > 0 {
+        builder = builder.num_threads(jobs);
+    }
+    builder
+        .build()
+        .expect("failed to build analysis thread pool")
+}
+
+/// Extract artifacts for every file in parallel, then merge into a
+/// single map keyed by path — not content hash, since two distinct
+/// files with byte-identical content (an empty `mod.rs`, a boilerplate
+/// stub repeated across modules) would otherwise collide on the same
+/// key and one file's entire symbol set would silently vanish from the
+/// graph — with symbols and edges sorted into a stable order, so output
+/// doesn't vary run-to-run.
+pub fn extract_all(
+    pool: &rayon::ThreadPool,
+    files: &[SourceFile],
+    extract: impl Fn(&Path, &[u8]) -> FileArtifacts + Sync,
+) -> BTreeMap<PathBuf, FileArtifacts> {
+    let extracted: Vec<(PathBuf, FileArtifacts)> = pool.install(|| {
+        files
+            .par_iter()
+            .map(|file| {
+                let mut artifacts = extract(&file.path, &file.content);
+                artifacts.symbols.sort_by_key(|s| s.id);
+                artifacts.edges.sort_by_key(|e| (e.from, e.to));
+                (file.path.clone(), artifacts)
+            })
+            .collect()
+    });
+
+    extracted.into_iter().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::cache::content_hash;
+    use crate::analysis::model::{Symbol, SymbolKind, Visibility};
+
+    fn fake_extract(path: &Path, content: &[u8]) -> FileArtifacts {
+        FileArtifacts {
+            symbols: vec![Symbol {
+                id: content_hash(content) as u32,
+                name: path.to_string_lossy().into_owned(),
+                kind: SymbolKind::Function,
+                visibility: Visibility::Private,
+                file: path.to_path_buf(),
+                line: 1,
+                signature: String::new(),
+                arity: 0,
+                is_test: false,
+                is_bench: false,
+            }],
+            edges: Vec::new(),
+            pending_method_calls: Vec::new(),
+            pending_references: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn merged_output_does_not_depend_on_input_file_order() {
+        let forward = vec![
+            SourceFile {
+                path: PathBuf::from("a.rs"),
+                content: b"a".to_vec(),
+            },
+            SourceFile {
+                path: PathBuf::from("b.rs"),
+                content: b"b".to_vec(),
+            },
+        ];
+        let reversed: Vec<SourceFile> = forward
+            .iter()
+            .rev()
+            .map(|f| SourceFile {
+                path: f.path.clone(),
+                content: f.content.clone(),
+            })
+            .collect();
+
+        let pool = build_pool(2);
+        let forward_result = extract_all(&pool, &forward, fake_extract);
+        let reversed_result = extract_all(&pool, &reversed, fake_extract);
+
+        let forward_names: Vec<&str> = forward_result
+            .values()
+            .flat_map(|a| a.symbols.iter().map(|s| s.name.as_str()))
+            .collect();
+        let reversed_names: Vec<&str> = reversed_result
+            .values()
+            .flat_map(|a| a.symbols.iter().map(|s| s.name.as_str()))
+            .collect();
+
+        assert_eq!(
+            forward_names, reversed_names,
+            "extraction order shouldn't affect the merged, sorted output"
+        );
+    }
+
+    #[test]
+    fn two_files_with_identical_content_both_keep_their_symbols() {
+        let files = vec![
+            SourceFile {
+                path: PathBuf::from("a.rs"),
+                content: b"pub fn only_in_a() {}".to_vec(),
+            },
+            SourceFile {
+                path: PathBuf::from("b.rs"),
+                content: b"pub fn only_in_a() {}".to_vec(),
+            },
+        ];
+
+        let pool = build_pool(2);
+        let merged = extract_all(&pool, &files, fake_extract);
+
+        assert_eq!(
+            merged.len(),
+            2,
+            "two distinct files shouldn't collide just because their content hashes to the same value"
+        );
+    }
+}
@@ -0,0 +1,180 @@
+//! Whole-crate reachability analysis.
+//!
+//! Builds a call graph over every function and method in the crate
+//! (closures are not separate nodes: a call made inside a closure is an
+//! edge from the enclosing function or method) and runs a fixed-point
+//! worklist from a set of roots to determine
+//! which symbols are actually reachable. Unlike naive "who calls this
+//! function by name" scanning, edges are added both for direct calls and
+//! for places where a function's *address* is taken — stored in a slice
+//! or `Vec`, passed as an argument, or returned — since that escaped
+//! value keeps the referenced function alive even though no call site
+//! names it directly. A dead function whose path is referenced only by
+//! another dead function stays dead; one referenced by a reachable
+//! function becomes reachable, because the edge is only useful if the
+//! node it starts from is itself in the visited set.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use serde::{Deserialize, Serialize};
+
+use crate::analysis::model::{CallEdge, EdgeKind, Symbol, SymbolId, SymbolKind, Visibility};
+
+/// The call graph for one analysis run: every known symbol plus the
+/// edges between them. Serializable so a run's merged graph can be
+/// persisted to the analysis cache alongside its per-file artifacts.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct CallGraph {
+    symbols: HashMap<SymbolId, Symbol>,
+    edges: HashMap<SymbolId, Vec<CallEdge>>,
+}
+
+impl CallGraph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Insert a symbol discovered by extraction. Ids are derived
+    /// deterministically from `(file, name, line)`
+    /// ([`crate::analysis::extract`]), so two distinct declarations
+    /// landing on the same id would mean that scheme has broken down —
+    /// worth catching in debug builds rather than silently merging two
+    /// unrelated symbols into one graph node.
+    pub fn add_symbol(&mut self, symbol: Symbol) {
+        match self.symbols.entry(symbol.id) {
+            std::collections::hash_map::Entry::Occupied(existing) => {
+                debug_assert!(
+                    existing.get().file == symbol.file && existing.get().name == symbol.name,
+                    "symbol id collision: {:?} and {:?} both map to id {}",
+                    existing.get(),
+                    symbol,
+                    symbol.id
+                );
+            }
+            std::collections::hash_map::Entry::Vacant(slot) => {
+                slot.insert(symbol);
+            }
+        }
+    }
+
+    /// Record a direct call from `from` to `to`.
+    pub fn add_call(&mut self, from: SymbolId, to: SymbolId) {
+        self.add_edge(from, to, EdgeKind::Call);
+    }
+
+    /// Record that `from` takes `to`'s address as a value (stored,
+    /// passed, or returned), letting it escape.
+    pub fn add_value_escape(&mut self, from: SymbolId, to: SymbolId) {
+        self.add_edge(from, to, EdgeKind::ValueEscape);
+    }
+
+    fn add_edge(&mut self, from: SymbolId, to: SymbolId, kind: EdgeKind) {
+        self.edges
+            .entry(from)
+            .or_default()
+            .push(CallEdge { from, to, kind });
+    }
+
+    /// Merge in an edge already classified by [`EdgeKind`] — used when
+    /// replaying cached per-file artifacts, which store edges this way.
+    pub fn merge_edge(&mut self, edge: CallEdge) {
+        self.add_edge(edge.from, edge.to, edge.kind);
+    }
+
+    pub fn symbol(&self, id: SymbolId) -> Option<&Symbol> {
+        self.symbols.get(&id)
+    }
+
+    pub fn symbols(&self) -> impl Iterator<Item = &Symbol> {
+        self.symbols.values()
+    }
+
+    /// Roots seeded into the reachability worklist: every symbol named
+    /// `main`, test and bench items, and — for library crates — every
+    /// symbol declared `pub`, in whichever file or module it is declared.
+    /// Whether a `pub` item is actually exported from the crate root is
+    /// not checked.
+    pub fn roots(&self, is_library: bool) -> Vec<SymbolId> {
+        self.symbols
+            .values()
+            .filter(|s| {
+                s.name == "main"
+                    || s.is_test
+                    || s.is_bench
+                    || (is_library && s.visibility == Visibility::Public)
+            })
+            .map(|s| s.id)
+            .collect()
+    }
+
+    /// Conservatively resolve a method call when the receiver's type
+    /// isn't known: every method with a matching name and arity is a
+    /// possible target, so all of them are treated as called.
+    pub fn resolve_method_candidates(&self, method_name: &str, arity: usize) -> Vec<SymbolId> {
+        self.symbols
+            .values()
+            .filter(|s| s.kind == SymbolKind::Method && s.name == method_name && s.arity == arity)
+            .map(|s| s.id)
+            .collect()
+    }
+
+    /// Resolve a plain named reference left unresolved by single-file
+    /// extraction — a call or value escape pointing at a function
+    /// declared in another file — by exact name match against every
+    /// known free function.
+    pub fn resolve_function_candidates(&self, name: &str) -> Vec<SymbolId> {
+        self.symbols
+            .values()
+            .filter(|s| s.kind == SymbolKind::Function && s.name == name)
+            .map(|s| s.id)
+            .collect()
+    }
+}
+
+/// Run the fixed-point worklist from `roots` and return the set of
+/// reachable symbol ids.
+pub fn compute_reachable(graph: &CallGraph, roots: &[SymbolId]) -> HashSet<SymbolId> {
+    compute_reachable_cancelable(graph, roots, &std::sync::atomic::AtomicBool::new(false))
+}
+
+/// Like [`compute_reachable`], but checks `cancel` between worklist
+/// iterations so a caller running this on a background task can abort a
+/// long scan early and still get back whatever was visited so far.
+pub fn compute_reachable_cancelable(
+    graph: &CallGraph,
+    roots: &[SymbolId],
+    cancel: &std::sync::atomic::AtomicBool,
+) -> HashSet<SymbolId> {
+    use std::sync::atomic::Ordering;
+
+    let mut visited: HashSet<SymbolId> = HashSet::new();
+    let mut worklist: VecDeque<SymbolId> = VecDeque::new();
+
+    for &root in roots {
+        if visited.insert(root) {
+            worklist.push_back(root);
+        }
+    }
+
+    while let Some(node) = worklist.pop_front() {
+        if cancel.load(Ordering::Relaxed) {
+            break;
+        }
+        for edge in graph.edges.get(&node).into_iter().flatten() {
+            if visited.insert(edge.to) {
+                worklist.push_back(edge.to);
+            }
+        }
+    }
+
+    visited
+}
+
+/// Every known symbol that `compute_reachable` did not visit — the
+/// dead-code report.
+pub fn dead_symbols<'a>(graph: &'a CallGraph, reachable: &HashSet<SymbolId>) -> Vec<&'a Symbol> {
+    graph
+        .symbols()
+        .filter(|s| !reachable.contains(&s.id))
+        .collect()
+}
@@ -0,0 +1,247 @@
+//! Typo-tolerant, ranked symbol search.
+//!
+//! Indexes every symbol discovered during analysis and answers fuzzy
+//! queries so an agent can find `split_and_trim` by typing `splittrim`
+//! or a misspelling. A prefix trie handles exact/prefix hits cheaply; a
+//! trigram inverted index generates fuzzy candidates for everything
+//! else. The union is ranked by a composite score: edit distance to the
+//! query, a prefix/substring bonus, symbol kind weight, and whether the
+//! reachability pass ([`crate::analysis::reachability`]) flagged the
+//! symbol as reachable.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::analysis::model::{Symbol, SymbolId, SymbolKind, Visibility};
+
+/// A minimal prefix trie over lowercase symbol names.
+#[derive(Debug, Default)]
+struct Trie {
+    children: HashMap<char, Trie>,
+    symbols: Vec<SymbolId>,
+}
+
+impl Trie {
+    fn insert(&mut self, name: &str, id: SymbolId) {
+        let mut node = self;
+        for ch in name.chars() {
+            node = node.children.entry(ch).or_default();
+            node.symbols.push(id);
+        }
+    }
+
+    /// Symbol ids for every indexed name starting with `prefix`.
+    fn prefix_matches(&self, prefix: &str) -> Vec<SymbolId> {
+        let mut node = self;
+        for ch in prefix.chars() {
+            match node.children.get(&ch) {
+                Some(next) => node = next,
+                None => return Vec::new(),
+            }
+        }
+        node.symbols.clone()
+    }
+}
+
+/// Inverted index from lowercase trigrams to the symbols whose name
+/// contains them, used to generate fuzzy candidates without scanning
+/// every symbol on each query.
+#[derive(Debug, Default)]
+struct TrigramIndex {
+    postings: HashMap<[char; 3], Vec<SymbolId>>,
+}
+
+impl TrigramIndex {
+    fn insert(&mut self, name: &str, id: SymbolId) {
+        for trigram in trigrams(name) {
+            self.postings.entry(trigram).or_default().push(id);
+        }
+    }
+
+    /// Candidate symbols sharing at least one trigram with `query`,
+    /// ordered by how many trigrams they share.
+    fn candidates(&self, query: &str) -> Vec<SymbolId> {
+        let mut counts: HashMap<SymbolId, usize> = HashMap::new();
+        for trigram in trigrams(query) {
+            for &id in self.postings.get(&trigram).into_iter().flatten() {
+                *counts.entry(id).or_insert(0) += 1;
+            }
+        }
+        let mut ranked: Vec<(SymbolId, usize)> = counts.into_iter().collect();
+        ranked.sort_by_key(|&(_, count)| std::cmp::Reverse(count));
+        ranked.into_iter().map(|(id, _)| id).collect()
+    }
+}
+
+fn trigrams(s: &str) -> Vec<[char; 3]> {
+    let chars: Vec<char> = s.chars().collect();
+    if chars.len() < 3 {
+        return Vec::new();
+    }
+    chars.windows(3).map(|w| [w[0], w[1], w[2]]).collect()
+}
+
+/// One ranked search result.
+#[derive(Debug)]
+pub struct SearchHit<'a> {
+    pub symbol: &'a Symbol,
+    pub score: f64,
+}
+
+/// Combined exact/prefix + fuzzy index over a crate's symbols.
+#[derive(Debug, Default)]
+pub struct SymbolIndex {
+    symbols: HashMap<SymbolId, Symbol>,
+    reachable: HashSet<SymbolId>,
+    trie: Trie,
+    trigrams: TrigramIndex,
+}
+
+impl SymbolIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn index(&mut self, symbol: Symbol) {
+        let key = symbol.name.to_lowercase();
+        self.trie.insert(&key, symbol.id);
+        self.trigrams.insert(&key, symbol.id);
+        self.symbols.insert(symbol.id, symbol);
+    }
+
+    /// Record which symbols the reachability pass flagged as reachable,
+    /// so search results can be ranked accordingly.
+    pub fn set_reachable(&mut self, reachable: HashSet<SymbolId>) {
+        self.reachable = reachable;
+    }
+
+    /// Answer a fuzzy query, ranked most to least relevant, keeping at
+    /// most `limit` hits.
+    pub fn search(&self, query: &str, limit: usize) -> Vec<SearchHit<'_>> {
+        let key = query.to_lowercase();
+        let mut candidates: HashSet<SymbolId> = self.trie.prefix_matches(&key).into_iter().collect();
+        candidates.extend(self.trigrams.candidates(&key));
+
+        let mut hits: Vec<SearchHit<'_>> = candidates
+            .into_iter()
+            .filter_map(|id| self.symbols.get(&id))
+            .map(|symbol| SearchHit {
+                score: self.score(&key, symbol),
+                symbol,
+            })
+            .collect();
+
+        hits.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap());
+        hits.truncate(limit);
+        hits
+    }
+
+    fn score(&self, query: &str, symbol: &Symbol) -> f64 {
+        let name = symbol.name.to_lowercase();
+        let distance = edit_distance(query, &name) as f64;
+        let max_len = query.len().max(name.len()).max(1) as f64;
+        let mut score = 1.0 - (distance / max_len);
+
+        if name.starts_with(query) {
+            score += 0.5;
+        } else if name.contains(query) {
+            score += 0.25;
+        }
+
+        score += match symbol.kind {
+            SymbolKind::Function | SymbolKind::Method => 0.1,
+            _ => 0.0,
+        };
+        score += match symbol.visibility {
+            Visibility::Public => 0.2,
+            Visibility::PublicCrate => 0.1,
+            Visibility::Private => 0.0,
+        };
+        if self.reachable.contains(&symbol.id) {
+            score += 0.15;
+        }
+
+        score
+    }
+}
+
+/// Classic Levenshtein edit distance between two strings.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+
+    for i in 1..=a.len() {
+        let mut prev = row[0];
+        row[0] = i;
+        for j in 1..=b.len() {
+            let temp = row[j];
+            row[j] = if a[i - 1] == b[j - 1] {
+                prev
+            } else {
+                1 + prev.min(row[j]).min(row[j - 1])
+            };
+            prev = temp;
+        }
+    }
+
+    row[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn symbol(id: SymbolId, name: &str) -> Symbol {
+        Symbol {
+            id,
+            name: name.to_string(),
+            kind: SymbolKind::Function,
+            visibility: Visibility::Private,
+            file: PathBuf::from("fixture.rs"),
+            line: 1,
+            signature: String::new(),
+            arity: 0,
+            is_test: false,
+            is_bench: false,
+        }
+    }
+
+    #[test]
+    fn a_misspelled_query_still_finds_the_symbol() {
+        let mut index = SymbolIndex::new();
+        index.index(symbol(1, "split_and_trim"));
+        index.index(symbol(2, "unrelated_helper"));
+
+        let hits = index.search("splittrim", 5);
+
+        assert!(
+            hits.iter().any(|hit| hit.symbol.name == "split_and_trim"),
+            "a fuzzy query for 'splittrim' should surface split_and_trim"
+        );
+    }
+
+    #[test]
+    fn a_struct_from_real_extraction_is_indexed_and_searchable() {
+        use crate::analysis::extract_file;
+        use std::fs;
+
+        let path = PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/testdata/languages/rust"))
+            .join("utils.rs");
+        let content = fs::read(&path).expect("fixture file is present");
+        let artifacts = extract_file(&path, &content);
+
+        let mut index = SymbolIndex::new();
+        for symbol in artifacts.symbols {
+            index.index(symbol);
+        }
+
+        for name in ["QueryBuilder", "Cache"] {
+            let hits = index.search(&name.to_lowercase(), 5);
+            assert!(
+                hits.iter().any(|hit| hit.symbol.name == name),
+                "{name} should be indexed from real extraction and findable by exact query"
+            );
+        }
+    }
+}
@@ -0,0 +1,8 @@
+//! Core analysis engine for mcp-codewizard.
+//!
+//! Scans a crate's source files, extracts symbols, and builds a call
+//! graph so that MCP tools can answer questions like "what's reachable
+//! from `main`" or "where is `split_and_trim` defined".
+
+pub mod analysis;
+pub mod service;
@@ -0,0 +1,10 @@
+//! Entry points into the analysis pipeline for MCP tools: a blocking
+//! call that runs everything and returns the finished report, and an
+//! async variant that streams progress and can be canceled early. See
+//! [`report::analyze`] and [`streaming::analyze_streaming`].
+
+pub mod report;
+pub mod streaming;
+
+pub use report::{analyze, AnalysisReport};
+pub use streaming::{analyze_streaming, AnalysisEvent, CancellationToken};
@@ -0,0 +1,218 @@
+//! The crate's synchronous analysis entry point: run the whole pipeline
+//! over a set of files and block until the final report is ready. See
+//! [`crate::service::streaming`] for the async, cancelable variant that
+//! yields progress as it goes.
+
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use crate::analysis::{
+    build_pool, compute_reachable, content_hash, dead_symbols, extract_all,
+    resolve_pending_method_calls, resolve_pending_references, AnalysisCache, CallGraph,
+    ContentHash, FileArtifacts, SourceFile, Symbol, SymbolId, SymbolIndex,
+};
+
+/// The result of a full analysis run: which symbols are reachable,
+/// which are dead, and a search index over everything that was found.
+pub struct AnalysisReport {
+    pub reachable: HashSet<SymbolId>,
+    pub dead: Vec<Symbol>,
+    pub index: SymbolIndex,
+}
+
+/// The graph key the merged call graph is stored under in the cache.
+/// There's only ever one merged graph per run, so a constant key is
+/// enough — it's overwritten wholesale on every write.
+const CACHED_GRAPH_KEY: &str = "main";
+
+/// Analyze `files` — parsing and extraction run across `jobs` threads —
+/// and block until the full report is ready. `is_library` controls root
+/// seeding: set it for crates whose `pub` items are part of an external
+/// API surface and so must count as reachability roots; leave it unset
+/// for binary crates, where only `fn main` and test/bench items are
+/// actually reachable from outside the crate.
+///
+/// When `cache` is given, each file's content hash is looked up first;
+/// a hit reuses the cached artifacts and skips `extract` entirely, and
+/// a miss is written back (artifacts plus the freshly merged graph,
+/// transactionally) so the next run over mostly-unchanged input only
+/// re-parses what actually changed.
+pub fn analyze(
+    files: Vec<SourceFile>,
+    jobs: usize,
+    is_library: bool,
+    extract: impl Fn(&Path, &[u8]) -> FileArtifacts + Sync,
+    cache: Option<&AnalysisCache>,
+) -> AnalysisReport {
+    let pool = build_pool(jobs);
+
+    let mut per_file: BTreeMap<PathBuf, FileArtifacts> = BTreeMap::new();
+    let mut misses: Vec<SourceFile> = Vec::new();
+    let mut miss_hashes: Vec<(PathBuf, ContentHash)> = Vec::new();
+
+    for file in files {
+        let hash = content_hash(&file.content);
+        match cache.and_then(|c| c.get(&file.path, hash).ok().flatten()) {
+            Some(cached) => {
+                per_file.insert(file.path, cached);
+            }
+            None => {
+                miss_hashes.push((file.path.clone(), hash));
+                misses.push(file);
+            }
+        }
+    }
+
+    if !misses.is_empty() {
+        per_file.extend(extract_all(&pool, &misses, extract));
+    }
+
+    let mut graph = CallGraph::new();
+    let mut index = SymbolIndex::new();
+
+    for artifacts in per_file.values() {
+        for symbol in &artifacts.symbols {
+            graph.add_symbol(symbol.clone());
+            index.index(symbol.clone());
+        }
+        for edge in &artifacts.edges {
+            graph.merge_edge(*edge);
+        }
+    }
+
+    // Method calls and cross-file references can't be resolved until
+    // every file's declarations are known, so they're replayed against
+    // the merged graph here.
+    for artifacts in per_file.values() {
+        resolve_pending_method_calls(&mut graph, &artifacts.pending_method_calls);
+        resolve_pending_references(&mut graph, &artifacts.pending_references);
+    }
+
+    if let Some(cache) = cache {
+        let graph_bytes = bincode::serialize(&graph).expect("serializable call graph");
+        for (path, hash) in &miss_hashes {
+            if let Some(artifacts) = per_file.get(path) {
+                let _ = cache.put_with_graph(path, *hash, artifacts, CACHED_GRAPH_KEY, &graph_bytes);
+            }
+        }
+    }
+
+    let roots = graph.roots(is_library);
+    let reachable = compute_reachable(&graph, &roots);
+    let dead = dead_symbols(&graph, &reachable)
+        .into_iter()
+        .cloned()
+        .collect();
+    index.set_reachable(reachable.clone());
+
+    AnalysisReport {
+        reachable,
+        dead,
+        index,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::extract_file;
+    use std::fs;
+    use std::path::PathBuf;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn fixture(name: &str) -> SourceFile {
+        let path = PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/testdata/languages/rust"))
+            .join(name);
+        let content = fs::read(&path).expect("fixture file is present");
+        SourceFile { path, content }
+    }
+
+    #[test]
+    fn classifies_reachable_and_dead_code_in_the_rust_fixture() {
+        let files = vec![fixture("main.rs"), fixture("utils.rs"), fixture("models.rs")];
+
+        let report = analyze(files, 1, false, extract_file, None);
+        let dead_names: HashSet<&str> = report.dead.iter().map(|s| s.name.as_str()).collect();
+
+        // Reachable from main, directly or transitively. `format_output`
+        // is only ever called as an argument inside a `println!` in
+        // `main`, so it also covers macro argument visiting (see
+        // `CallVisitor::visit_macro`); `process_data` is a direct call
+        // into another file, resolved as a pending reference.
+        for name in [
+            "main",
+            "load_config",
+            "run_pipeline",
+            "fetch_data",
+            "save_data",
+            "format_output",
+            "process_data",
+        ] {
+            assert!(!dead_names.contains(name), "{name} should be reachable");
+        }
+
+        // `parse_hex` is never called directly; its address escapes into
+        // the parser slice built by `validate_pipeline`, so it must be
+        // reachable via the escaped-function-value edge rather than a
+        // direct call edge.
+        assert!(
+            !dead_names.contains("parse_hex"),
+            "parse_hex should be reachable through the escaped function value in validate_pipeline"
+        );
+
+        // `Config::new`, `Server::new`, and `Logger::new` are all called
+        // through a qualified path rather than a receiver (`Config::new(...)`,
+        // `Server::new(config.clone())`, `Logger::new(name)`), so this
+        // covers `CallVisitor::visit_expr_call`'s qualified-path branch.
+        // Several unrelated `new` methods share the name (and some share
+        // the arity too), so these are checked by declaration line rather
+        // than by name, which a bare `dead_names.contains("new")` can't
+        // distinguish between.
+        let dead_new_lines: HashSet<u32> = report
+            .dead
+            .iter()
+            .filter(|s| s.name == "new" && s.file.ends_with("models.rs"))
+            .map(|s| s.line)
+            .collect();
+        for line in [17, 59, 106] {
+            assert!(
+                !dead_new_lines.contains(&line),
+                "the `new` declared at models.rs:{line} should be reachable through its qualified call"
+            );
+        }
+
+        // Plain dead code, and a dead chain reachable only from other
+        // dead code, must both stay dead.
+        for name in ["unused_function", "dead_chain_start", "dead_chain_middle", "dead_chain_end"] {
+            assert!(dead_names.contains(name), "{name} should be dead code");
+        }
+    }
+
+    #[test]
+    fn a_second_run_with_the_same_cache_skips_extraction_for_unchanged_files() {
+        let fixture_names = ["main.rs", "utils.rs", "models.rs"];
+        let cache = AnalysisCache::open_temporary().expect("open an in-memory cache for the test");
+
+        let calls = AtomicUsize::new(0);
+        let counting_extract = |path: &Path, content: &[u8]| {
+            calls.fetch_add(1, Ordering::Relaxed);
+            extract_file(path, content)
+        };
+
+        let files: Vec<SourceFile> = fixture_names.iter().map(|name| fixture(name)).collect();
+        analyze(files, 1, false, counting_extract, Some(&cache));
+        assert_eq!(
+            calls.load(Ordering::Relaxed),
+            fixture_names.len(),
+            "first run is all misses"
+        );
+
+        let files: Vec<SourceFile> = fixture_names.iter().map(|name| fixture(name)).collect();
+        analyze(files, 1, false, counting_extract, Some(&cache));
+        assert_eq!(
+            calls.load(Ordering::Relaxed),
+            fixture_names.len(),
+            "second run over unchanged content should hit the cache and not call extract again"
+        );
+    }
+}
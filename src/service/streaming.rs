@@ -0,0 +1,312 @@
+//! Asynchronous, cancelable analysis that streams progress as it runs.
+//!
+//! Mirrors [`crate::service::report::analyze`], driving the same
+//! pipeline but emitting a progress event per file as it is merged into
+//! the graph (once parallel extraction of every file has finished, so
+//! no event is sent while extraction is still running) and a partial
+//! dead-code list once reachability is computed, and stoppable early
+//! via a [`CancellationToken`] checked between files and between
+//! reachability worklist iterations. A cancellation never comes back
+//! empty-handed: [`AnalysisEvent::Canceled`] carries a
+//! [`PartialAnalysis`] built from whatever was merged into the graph
+//! before the cancellation was observed.
+
+use std::collections::HashSet;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use rayon::prelude::*;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use tokio_stream::Stream;
+
+use crate::analysis::{
+    build_pool, compute_reachable, compute_reachable_cancelable, dead_symbols,
+    resolve_pending_method_calls, resolve_pending_references, CallGraph, FileArtifacts, SourceFile,
+    Symbol, SymbolId, SymbolIndex,
+};
+use crate::service::report::AnalysisReport;
+
+/// A cooperative cancellation flag shared between the caller and a
+/// running scan. Cheap to clone; canceling through any clone cancels
+/// every in-flight check.
+#[derive(Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// One increment of progress from a streaming analysis run.
+pub enum AnalysisEvent {
+    FileParsed {
+        path: std::path::PathBuf,
+        symbols_found: usize,
+    },
+    PartialDeadCode(Vec<Symbol>),
+    Canceled(Box<PartialAnalysis>),
+    Done(Box<AnalysisReport>),
+}
+
+/// Whatever a canceled scan had computed up to the point the
+/// cancellation was observed: reachability and dead-code results over
+/// the files merged so far, and a search index over the symbols found
+/// in them — so a client that cancels a long-running scan gets back a
+/// usable (if incomplete) report instead of nothing.
+pub struct PartialAnalysis {
+    pub reachable: HashSet<SymbolId>,
+    pub dead: Vec<Symbol>,
+    pub index: SymbolIndex,
+}
+
+/// Run reachability over whatever's been merged into `graph` so far and
+/// package it as a [`PartialAnalysis`], for every cancellation point
+/// that doesn't already have a reachable set of its own to hand back.
+fn partial_analysis(graph: &CallGraph, mut index: SymbolIndex, is_library: bool) -> PartialAnalysis {
+    let roots = graph.roots(is_library);
+    let reachable = compute_reachable(graph, &roots);
+    let dead = dead_symbols(graph, &reachable).into_iter().cloned().collect();
+    index.set_reachable(reachable.clone());
+    PartialAnalysis {
+        reachable,
+        dead,
+        index,
+    }
+}
+
+/// Run the same pipeline as [`crate::service::report::analyze`], but as
+/// a stream of [`AnalysisEvent`]s instead of one blocking call, stopping
+/// early if `cancel` is triggered.
+pub fn analyze_streaming(
+    files: Vec<SourceFile>,
+    jobs: usize,
+    is_library: bool,
+    extract: impl Fn(&Path, &[u8]) -> FileArtifacts + Sync + Send + 'static,
+    cancel: CancellationToken,
+) -> impl Stream<Item = AnalysisEvent> {
+    let (tx, rx) = mpsc::channel(32);
+
+    std::thread::spawn(move || {
+        let pool = build_pool(jobs);
+        // Parsing and extraction genuinely run across the pool, same as
+        // the blocking `analyze` path, but — unlike that path — each file
+        // checks for cancellation before it starts, so a cancel during a
+        // large scan skips every file not yet started. Files already being
+        // extracted when the cancel arrives still run to completion.
+        let slots: Vec<Mutex<Option<FileArtifacts>>> = files.iter().map(|_| Mutex::new(None)).collect();
+        let completed = pool.install(|| {
+            files
+                .par_iter()
+                .zip(slots.par_iter())
+                .try_for_each(|(f, slot)| {
+                    if cancel.is_cancelled() {
+                        return Err(());
+                    }
+                    *slot.lock().unwrap() = Some(extract(&f.path, &f.content));
+                    Ok(())
+                })
+        });
+
+        if completed.is_err() || cancel.is_cancelled() {
+            // Whatever files finished extracting before cancellation was
+            // observed are still worth merging and reporting, rather
+            // than throwing them away.
+            let mut graph = CallGraph::new();
+            let mut index = SymbolIndex::new();
+            for slot in &slots {
+                if let Some(artifacts) = slot.lock().unwrap().as_ref() {
+                    for symbol in &artifacts.symbols {
+                        graph.add_symbol(symbol.clone());
+                        index.index(symbol.clone());
+                    }
+                    for edge in &artifacts.edges {
+                        graph.merge_edge(*edge);
+                    }
+                }
+            }
+            let partial = partial_analysis(&graph, index, is_library);
+            let _ = tx.blocking_send(AnalysisEvent::Canceled(Box::new(partial)));
+            return;
+        }
+
+        let mut graph = CallGraph::new();
+        let mut index = SymbolIndex::new();
+        let mut per_file = Vec::with_capacity(files.len());
+
+        for (file, slot) in files.iter().zip(slots) {
+            if cancel.is_cancelled() {
+                let partial = partial_analysis(&graph, index, is_library);
+                let _ = tx.blocking_send(AnalysisEvent::Canceled(Box::new(partial)));
+                return;
+            }
+
+            let artifacts = slot.into_inner().unwrap().expect("every slot filled before this loop");
+
+            for symbol in &artifacts.symbols {
+                graph.add_symbol(symbol.clone());
+                index.index(symbol.clone());
+            }
+            for edge in &artifacts.edges {
+                graph.merge_edge(*edge);
+            }
+
+            if tx
+                .blocking_send(AnalysisEvent::FileParsed {
+                    path: file.path.clone(),
+                    symbols_found: artifacts.symbols.len(),
+                })
+                .is_err()
+            {
+                return; // receiver dropped, nothing left to stream to
+            }
+
+            per_file.push(artifacts);
+        }
+
+        // Method calls and cross-file references can't be resolved until
+        // every file's declarations are known, so they're replayed
+        // against the merged graph here, same as the blocking path.
+        for artifacts in &per_file {
+            resolve_pending_method_calls(&mut graph, &artifacts.pending_method_calls);
+            resolve_pending_references(&mut graph, &artifacts.pending_references);
+        }
+
+        if cancel.is_cancelled() {
+            let partial = partial_analysis(&graph, index, is_library);
+            let _ = tx.blocking_send(AnalysisEvent::Canceled(Box::new(partial)));
+            return;
+        }
+
+        let roots = graph.roots(is_library);
+        let reachable = compute_reachable_cancelable(&graph, &roots, &cancel.0);
+        if cancel.is_cancelled() {
+            // The cancelable worklist already stopped early and handed
+            // back whatever it had visited, so there's no need to
+            // recompute reachability from scratch here.
+            let dead: Vec<Symbol> = dead_symbols(&graph, &reachable)
+                .into_iter()
+                .cloned()
+                .collect();
+            index.set_reachable(reachable.clone());
+            let _ = tx.blocking_send(AnalysisEvent::Canceled(Box::new(PartialAnalysis {
+                reachable,
+                dead,
+                index,
+            })));
+            return;
+        }
+
+        let dead: Vec<Symbol> = dead_symbols(&graph, &reachable)
+            .into_iter()
+            .cloned()
+            .collect();
+        let _ = tx.blocking_send(AnalysisEvent::PartialDeadCode(dead.clone()));
+
+        index.set_reachable(reachable.clone());
+        let _ = tx.blocking_send(AnalysisEvent::Done(Box::new(AnalysisReport {
+            reachable,
+            dead,
+            index,
+        })));
+    });
+
+    ReceiverStream::new(rx)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use tokio_stream::StreamExt;
+
+    #[tokio::test]
+    async fn a_cancellation_set_before_extraction_finishes_stops_the_stream_early() {
+        let files = vec![SourceFile {
+            path: PathBuf::from("a.rs"),
+            content: b"fn a() {}".to_vec(),
+        }];
+        let cancel = CancellationToken::new();
+        cancel.cancel();
+
+        let mut stream = Box::pin(analyze_streaming(
+            files,
+            1,
+            false,
+            |_, _| FileArtifacts::default(),
+            cancel,
+        ));
+
+        let first = stream
+            .next()
+            .await
+            .expect("a cancelled run should still yield one event");
+        let partial = match first {
+            AnalysisEvent::Canceled(partial) => partial,
+            _ => panic!("a run cancelled before extraction starts should yield Canceled, not reach Done"),
+        };
+        assert!(
+            partial.dead.is_empty(),
+            "nothing was extracted before cancellation, so the partial report should be empty"
+        );
+        assert!(
+            stream.next().await.is_none(),
+            "no events should follow Canceled"
+        );
+    }
+
+    #[tokio::test]
+    async fn a_cancellation_after_a_file_is_extracted_carries_it_in_the_partial_report() {
+        let files = vec![
+            SourceFile {
+                path: PathBuf::from("a.rs"),
+                content: b"fn a() {}".to_vec(),
+            },
+            SourceFile {
+                path: PathBuf::from("b.rs"),
+                content: b"fn b() {}".to_vec(),
+            },
+        ];
+        let cancel = CancellationToken::new();
+        let cancel_after_first = cancel.clone();
+
+        let mut stream = Box::pin(analyze_streaming(
+            files,
+            1,
+            false,
+            move |path, content| {
+                let artifacts = crate::analysis::extract_file(path, content);
+                if path == Path::new("a.rs") {
+                    cancel_after_first.cancel();
+                }
+                artifacts
+            },
+            cancel,
+        ));
+
+        let mut last = None;
+        while let Some(event) = stream.next().await {
+            last = Some(event);
+        }
+
+        match last.expect("a cancelled run should still yield a terminal event") {
+            AnalysisEvent::Canceled(partial) => {
+                assert!(
+                    partial.index.search("a", 5).iter().any(|hit| hit.symbol.name == "a"),
+                    "the file extracted before cancellation was observed should still show up in the partial report"
+                );
+            }
+            _ => panic!("a cancelled run should end in Canceled, not Done"),
+        }
+    }
+}
@@ -5,7 +5,7 @@ mod utils;
 mod models;
 
 use models::{Config, Server, Logger};
-use utils::{process_data, format_output};
+use utils::{process_data, format_output, try_parse, parse_hex};
 
 /// Maximum retries constant - tests constant extraction.
 const MAX_RETRIES: u32 = 3;
@@ -68,6 +68,17 @@ fn run_pipeline() {
     let data = fetch_data();
     let transformed = transform_data(&data);
     save_data(&transformed);
+    validate_pipeline(&transformed);
+}
+
+/// Validate pipeline output against a registered set of parsers - tests
+/// escaped-function-value reachability: `parse_hex` is never called
+/// directly, but its address escapes into this slice, so it should be
+/// marked reachable.
+fn validate_pipeline(data: &[u8]) {
+    let parsers: [fn(&str) -> Result<i32, String>; 1] = [parse_hex];
+    let text = String::from_utf8_lossy(data);
+    let _ = try_parse(&text, &parsers);
 }
 
 /// Fetch data - called by run_pipeline, should be reachable.